@@ -0,0 +1,60 @@
+// Platform-neutral readiness interest: the input-side counterpart to
+// `Event`'s `is_readable`/`is_writable` introspection methods, using the
+// same readable/writable/edge-triggered/one-shot vocabulary. Each backend
+// under `sys` translates this into its own native representation: an epoll
+// bitmask on Linux, or `EVFILT_READ`/`EVFILT_WRITE` kevent filters on
+// macOS, iOS and OpenBSD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interest(u8);
+
+const READABLE: u8 = 0b0001;
+const WRITABLE: u8 = 0b0010;
+const EDGE_TRIGGERED: u8 = 0b0100;
+const ONESHOT: u8 = 0b1000;
+
+impl Interest {
+    pub const READABLE: Interest = Interest(READABLE);
+    pub const WRITABLE: Interest = Interest(WRITABLE);
+
+    // Combine this interest with another, e.g. `Interest::READABLE.with(Interest::WRITABLE)`.
+    pub fn with(self, other: Interest) -> Interest {
+        Interest(self.0 | other.0)
+    }
+
+    // Requests edge-triggered rather than level-triggered notification
+    // (`EPOLLET` on Linux, `EV_CLEAR` on kqueue).
+    pub fn edge_triggered(self) -> Interest {
+        Interest(self.0 | EDGE_TRIGGERED)
+    }
+
+    // After the next event fires, disables the source until it is re-armed
+    // via `Registry::reregister` (`EPOLLONESHOT` on Linux, `EV_ONESHOT` on
+    // kqueue).
+    pub fn oneshot(self) -> Interest {
+        Interest(self.0 | ONESHOT)
+    }
+
+    pub(crate) fn is_readable(&self) -> bool {
+        self.0 & READABLE != 0
+    }
+
+    pub(crate) fn is_writable(&self) -> bool {
+        self.0 & WRITABLE != 0
+    }
+
+    pub(crate) fn is_edge_triggered(&self) -> bool {
+        self.0 & EDGE_TRIGGERED != 0
+    }
+
+    pub(crate) fn is_oneshot(&self) -> bool {
+        self.0 & ONESHOT != 0
+    }
+}
+
+impl std::ops::BitOr for Interest {
+    type Output = Interest;
+
+    fn bitor(self, rhs: Interest) -> Interest {
+        self.with(rhs)
+    }
+}
@@ -0,0 +1,19 @@
+//! `epore` is a minimal readiness-based event registry (epoll on Linux,
+//! kqueue on macOS, iOS and OpenBSD; other BSDs have no backend), published
+//! here as a library so the `Registry`,
+//! `Waker`, and `Source` APIs are real, reachable surface rather than
+//! dead code behind the demo binary in `main.rs`.
+
+pub mod interest;
+pub mod poll;
+pub mod source;
+pub mod sys;
+#[cfg(target_os = "linux")]
+pub mod waker;
+
+pub use interest::Interest;
+pub use poll::{Poll, Registry};
+pub use source::{Source, SourceFd};
+pub use sys::Event;
+#[cfg(target_os = "linux")]
+pub use waker::Waker;
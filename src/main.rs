@@ -7,13 +7,9 @@ use std::{
 };
 
 use bytes::{BufMut, BytesMut};
-use ffi::Event;
-use poll::Poll;
+use epore::{interest::Interest, poll::Poll, sys::Event};
 use rand::Rng;
 
-mod ffi;
-mod poll;
-
 // build our request as a buffer of bytes (&[u8])
 //
 // NOTE: BytesMut does implement AsRef so that
@@ -81,7 +77,8 @@ fn handle_event(
 fn main() -> Result<()> {
     // The Event "queue":
     // not really,
-    // just the interface to Linux's epoll_queue
+    // just the interface to the OS readiness queue (epoll on Linux, kqueue
+    // on macOS, iOS and OpenBSD)
     let mut epoll_interface_registry = Poll::new().expect("Can't run epoll_create.");
 
     // aka how many requests do we want to send
@@ -129,11 +126,11 @@ fn main() -> Result<()> {
         // register interests
         // for when data is ready to be READ
         // from this TcpStream,
-        // edge-triggered by EPOLLET
+        // edge-triggered
         epoll_interface_registry
             .registry()
             // the request_id is also the token for file descriptor ID purposes
-            .register(&tcp_stream, request_id, ffi::EPOLLET | ffi::EPOLLIN)
+            .register(&tcp_stream, request_id, Interest::READABLE.edge_triggered())
             .unwrap_or_else(|_| {
                 panic!("Failed to register interests in the event queue for {request_id}")
             });
@@ -157,7 +154,7 @@ fn main() -> Result<()> {
     while handled_events < number_of_events {
         // too low of a number would limit
         // how many events the OS could notify us
-        // on each wake up (see: EPOLLET)
+        // on each wake up (see: edge-triggered interest)
         let mut events_buffer: Vec<Event> = Vec::with_capacity(20);
 
         // when epoll_wait success, number of file descriptors
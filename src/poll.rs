@@ -1,48 +1,42 @@
-use crate::ffi;
-use std::{
-    io::{self, Result},
-    net::TcpStream,
-    os::fd::AsRawFd,
-};
+use crate::interest::Interest;
+use crate::source::Source;
+use crate::sys;
+use std::io::Result;
 
 // We can be interested in multiple events
-type Events = Vec<ffi::Event>;
+type Events = Vec<sys::Event>;
 
-// The file descriptor of our target (could be a TCP socket or a TcpStream in our case)
+// A thin, platform-neutral wrapper over whichever `sys::Selector` backend
+// was selected for the target OS (epoll on Linux, kqueue on macOS, iOS and
+// OpenBSD).
 pub struct Registry {
-    raw_fd: i32,
+    selector: sys::Selector,
 }
 
 impl Registry {
     // Register interest by adding it
-    // TcpStream is a high level representation of a TCP socket file descriptor
+    // `source` can be any type that owns a file descriptor (a `TcpStream`,
+    // a `UdpSocket`, or a raw fd wrapped in `SourceFd`)
     // token is too differentiate from different file descriptor, as a label
-    pub fn register(&self, source: &TcpStream, token: usize, interests: i32) -> Result<()> {
-        match unsafe {
-            ffi::epoll_ctl(
-                self.raw_fd,
-                ffi::EPOLL_CTL_ADD,
-                source.as_raw_fd(),
-                &mut ffi::Event {
-                    events: interests as u32,
-                    epoll_data: token,
-                },
-            )
-        } {
-            exit_code if exit_code < 0 => Err(io::Error::last_os_error()),
-            _ => Ok(()),
-        }
+    pub fn register<S: Source>(&self, source: &S, token: usize, interest: Interest) -> Result<()> {
+        self.selector.register(source.as_raw_fd(), token, interest)
     }
-}
 
-impl Drop for Registry {
-    fn drop(&mut self) {
-        let res = unsafe { ffi::close(self.raw_fd) };
+    // Change the interest set of an already-registered source,
+    // e.g. switching from read-interest to write-interest after a partial write
+    pub fn reregister<S: Source>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> Result<()> {
+        self.selector
+            .reregister(source.as_raw_fd(), token, interest)
+    }
 
-        if res < 0 {
-            let err = io::Error::last_os_error();
-            eprintln!("ERROR: {err:?}");
-        }
+    // Remove a source from the interest list, e.g. before closing it
+    pub fn deregister<S: Source>(&self, source: &S) -> Result<()> {
+        self.selector.deregister(source.as_raw_fd())
     }
 }
 
@@ -52,12 +46,10 @@ pub struct Poll {
 
 impl Poll {
     pub fn new() -> Result<Self> {
-        let res = unsafe { ffi::epoll_create(1) };
-        if res < 0 {
-            return Err(io::Error::last_os_error());
-        }
         Ok(Self {
-            registry: Registry { raw_fd: res },
+            registry: Registry {
+                selector: sys::Selector::new()?,
+            },
         })
     }
 
@@ -65,25 +57,11 @@ impl Poll {
         &self.registry
     }
 
+    // Note for callers using a `Waker`: an event whose `token()` matches the
+    // waker's reserved token must be followed by `Waker::drain()` before
+    // the next call to `poll`, or the waker's eventfd stays readable and
+    // this will keep returning immediately instead of blocking.
     pub fn poll(&mut self, events: &mut Events, timeout: Option<i32>) -> Result<()> {
-        let fd = self.registry.raw_fd;
-
-        let timeout = timeout.unwrap_or(-1);
-
-        let max_events = events.capacity() as i32;
-
-        let res = unsafe { ffi::epoll_wait(fd, events.as_mut_ptr(), max_events, timeout) };
-
-        if res < 0 {
-            return Err(io::Error::last_os_error());
-        };
-
-        // when epoll_wait success, number of file descriptors
-        // ready for the requested I/O operation, or zero if no file
-        // descriptor became ready during the requested timeout
-        // milliseconds
-        unsafe { events.set_len(res as usize) };
-
-        Ok(())
+        self.registry.selector.select(events, timeout)
     }
 }
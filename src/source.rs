@@ -0,0 +1,19 @@
+use std::os::fd::{AsRawFd, RawFd};
+
+// Anything that owns a file descriptor and can therefore be registered with
+// a `Registry`: a `TcpStream`, `UdpSocket`, `TcpListener`, `UnixStream`, or a
+// raw fd from another library via `SourceFd`.
+pub trait Source: AsRawFd {}
+
+impl<T: AsRawFd> Source for T {}
+
+// Wraps a raw file descriptor so it can be registered the same way as any
+// other `Source`, e.g. a `timerfd` or `signalfd` that doesn't have its own
+// Rust type. Mirrors mio's `SourceFd`.
+pub struct SourceFd(pub RawFd);
+
+impl AsRawFd for SourceFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
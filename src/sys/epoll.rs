@@ -0,0 +1,246 @@
+use crate::interest::Interest;
+use std::io::{self, Result};
+
+// Register interest
+const EPOLL_CTL_ADD: i32 = 1;
+
+// Remove the target file descriptor from the epoll instance
+const EPOLL_CTL_DEL: i32 = 2;
+
+// Change the settings associated with an already-registered file descriptor
+const EPOLL_CTL_MOD: i32 = 3;
+
+// Bit mask so express that
+// we are interest when the data is available to READ
+const EPOLLIN: u32 = 0x1;
+
+// Bit mask to express that we are interested in being notified
+// when the file descriptor is available to WRITE
+const EPOLLOUT: u32 = 0x4;
+
+// Bit mask for requests
+// edge-triggered notification
+// for the associated file descriptor.
+// The default behavior for epoll is level-triggered.
+const EPOLLET: u32 = 1 << 31;
+
+// Set by the kernel on the returned event when an error condition happened
+// on the associated file descriptor; always reported, no need to set it
+// as an interest.
+const EPOLLERR: u32 = 0x8;
+
+// Set by the kernel when the peer closed the connection, or shut down the
+// writing half; always reported, no need to set it as an interest.
+const EPOLLHUP: u32 = 0x10;
+
+// Set by the kernel when the peer shut down its writing half while leaving
+// ours open (half-close), i.e. a "read hang up".
+const EPOLLRDHUP: u32 = 0x2000;
+
+// Requests one-shot notification: after the next event fires, the source is
+// disabled and must be re-armed via `Registry::reregister` before it can
+// report further events.
+const EPOLLONESHOT: u32 = 1 << 30;
+
+// Close the epoll file descriptor automatically on exec, passed to
+// `epoll_create1`.
+const EPOLL_CLOEXEC: i32 = 0x80000;
+
+// Make the eventfd non-blocking, same as set_nonblocking on a TcpStream
+pub(crate) const EFD_NONBLOCK: i32 = 0x800;
+
+// Close the eventfd automatically on exec, same rationale as EPOLL_CLOEXEC
+pub(crate) const EFD_CLOEXEC: i32 = 0x80000;
+
+// Avoid padding by using repr(packed)
+// Data struct is different in Rust compared to C
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+#[cfg_attr(target_arch = "x86_64", repr(packed))]
+struct RawEvent {
+    events: u32,
+    // Using `Token` a.k.a `epoll_data` to track which socket generated the event
+    epoll_data: usize,
+}
+
+// Here we have the syscalls
+// Unsafe !!!
+#[link(name = "c")]
+extern "C" {
+    // Superseded `epoll_create(size)`: the `size` argument is obsolete
+    // (ignored by the kernel) and, unlike this call, it leaves the epoll fd
+    // open across `exec`. Use `EPOLL_CLOEXEC` to get close-on-exec.
+    fn epoll_create1(flags: i32) -> i32;
+    pub(crate) fn close(fd: i32) -> i32;
+    fn epoll_ctl(epfd: i32, op: i32, fd: i32, event: *mut RawEvent) -> i32;
+    fn epoll_wait(epfd: i32, events: *mut RawEvent, maxevents: i32, timeout: i32) -> i32;
+
+    // Creates a file descriptor that can be used to wake up a blocked
+    // epoll_wait from another thread by writing to it
+    pub(crate) fn eventfd(initval: u32, flags: i32) -> i32;
+    pub(crate) fn read(fd: i32, buf: *mut u8, count: usize) -> isize;
+    pub(crate) fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+}
+
+// The epoll-native readiness event, wrapping `RawEvent` so the memory
+// layout epoll_wait writes into matches what `Vec<Event>` hands it.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct Event(RawEvent);
+
+impl Event {
+    pub fn token(&self) -> usize {
+        self.0.epoll_data
+    }
+
+    // `events` is a packed field, so it can't be borrowed directly without
+    // triggering unaligned-reference UB; copy it into a local first.
+    fn events(&self) -> u32 {
+        self.0.events
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.events() & EPOLLIN != 0
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.events() & EPOLLOUT != 0
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.events() & EPOLLERR != 0
+    }
+
+    // True when the peer hung up or shut down the writing half, i.e. there
+    // is nothing more to read, as distinct from a true error (`is_error`).
+    pub fn is_read_closed(&self) -> bool {
+        let events = self.events();
+        events & EPOLLHUP != 0 || events & EPOLLRDHUP != 0
+    }
+
+    pub fn is_write_closed(&self) -> bool {
+        self.events() & EPOLLHUP != 0
+    }
+}
+
+fn to_raw_events(interest: Interest) -> u32 {
+    let mut events = 0;
+
+    if interest.is_readable() {
+        // EPOLLRDHUP is only reported when requested, unlike EPOLLHUP
+        events |= EPOLLIN | EPOLLRDHUP;
+    }
+    if interest.is_writable() {
+        events |= EPOLLOUT;
+    }
+    if interest.is_edge_triggered() {
+        events |= EPOLLET;
+    }
+    if interest.is_oneshot() {
+        events |= EPOLLONESHOT;
+    }
+
+    events
+}
+
+// The file descriptor of the underlying epoll instance
+pub struct Selector {
+    raw_fd: i32,
+}
+
+impl Selector {
+    pub fn new() -> Result<Self> {
+        let res = unsafe { epoll_create1(EPOLL_CLOEXEC) };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { raw_fd: res })
+    }
+
+    pub fn register(&self, fd: i32, token: usize, interest: Interest) -> Result<()> {
+        match unsafe {
+            epoll_ctl(
+                self.raw_fd,
+                EPOLL_CTL_ADD,
+                fd,
+                &mut RawEvent {
+                    events: to_raw_events(interest),
+                    epoll_data: token,
+                },
+            )
+        } {
+            exit_code if exit_code < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn reregister(&self, fd: i32, token: usize, interest: Interest) -> Result<()> {
+        match unsafe {
+            epoll_ctl(
+                self.raw_fd,
+                EPOLL_CTL_MOD,
+                fd,
+                &mut RawEvent {
+                    events: to_raw_events(interest),
+                    epoll_data: token,
+                },
+            )
+        } {
+            exit_code if exit_code < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn deregister(&self, fd: i32) -> Result<()> {
+        // The kernel ignores the event pointer for EPOLL_CTL_DEL on modern
+        // Linux, but kernels older than 2.6.9 require a non-null pointer,
+        // so we pass a dummy RawEvent rather than null.
+        let mut dummy_event = RawEvent {
+            events: 0,
+            epoll_data: 0,
+        };
+
+        match unsafe { epoll_ctl(self.raw_fd, EPOLL_CTL_DEL, fd, &mut dummy_event) } {
+            exit_code if exit_code < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn select(&self, events: &mut Vec<Event>, timeout: Option<i32>) -> Result<()> {
+        let timeout = timeout.unwrap_or(-1);
+
+        let max_events = events.capacity() as i32;
+
+        let res = unsafe {
+            epoll_wait(
+                self.raw_fd,
+                events.as_mut_ptr() as *mut RawEvent,
+                max_events,
+                timeout,
+            )
+        };
+
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // when epoll_wait success, number of file descriptors
+        // ready for the requested I/O operation, or zero if no file
+        // descriptor became ready during the requested timeout
+        // milliseconds
+        unsafe { events.set_len(res as usize) };
+
+        Ok(())
+    }
+}
+
+impl Drop for Selector {
+    fn drop(&mut self) {
+        let res = unsafe { close(self.raw_fd) };
+
+        if res < 0 {
+            let err = io::Error::last_os_error();
+            eprintln!("ERROR: {err:?}");
+        }
+    }
+}
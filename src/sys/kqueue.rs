@@ -0,0 +1,247 @@
+use crate::interest::Interest;
+use std::io::{self, Result};
+
+const EVFILT_READ: i16 = -1;
+const EVFILT_WRITE: i16 = -2;
+
+const EV_ADD: u16 = 0x0001;
+const EV_DELETE: u16 = 0x0002;
+const EV_ENABLE: u16 = 0x0004;
+const EV_ONESHOT: u16 = 0x0010;
+const EV_CLEAR: u16 = 0x0020;
+const EV_EOF: u16 = 0x8000;
+const EV_ERROR: u16 = 0x4000;
+
+// Registering a filter that was never added back out with EV_DELETE fails
+// with ENOENT; harmless since a source only has the filters it was
+// registered for.
+const ENOENT: i32 = 2;
+
+#[repr(C)]
+struct Timespec {
+    tv_sec: i64,
+    tv_nsec: i64,
+}
+
+// The kqueue-native change/event record. The same `kevent` struct doubles
+// as both the "changelist" we submit and the "eventlist" we read back.
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+struct RawEvent {
+    ident: usize,
+    filter: i16,
+    flags: u16,
+    fflags: u32,
+    data: isize,
+    udata: usize,
+}
+
+#[link(name = "c")]
+extern "C" {
+    fn kqueue() -> i32;
+    fn kevent(
+        kq: i32,
+        changelist: *const RawEvent,
+        nchanges: i32,
+        eventlist: *mut RawEvent,
+        nevents: i32,
+        timeout: *const Timespec,
+    ) -> i32;
+    fn close(fd: i32) -> i32;
+}
+
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct Event(RawEvent);
+
+impl Event {
+    // The token is carried in `udata`, mirroring how `epoll_data` carries
+    // it on Linux.
+    pub fn token(&self) -> usize {
+        self.0.udata
+    }
+
+    // A record with `EV_ERROR` set (e.g. a bad fd rejected out of the
+    // changelist) carries an errno in `data`, not real readiness, so it
+    // must not be reported as readable/writable even though its `filter`
+    // still names `EVFILT_READ`/`EVFILT_WRITE`.
+    pub fn is_readable(&self) -> bool {
+        self.0.filter == EVFILT_READ && !self.is_error()
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.0.filter == EVFILT_WRITE && !self.is_error()
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.0.flags & EV_ERROR != 0
+    }
+
+    pub fn is_read_closed(&self) -> bool {
+        self.is_readable() && self.0.flags & EV_EOF != 0
+    }
+
+    pub fn is_write_closed(&self) -> bool {
+        self.is_writable() && self.0.flags & EV_EOF != 0
+    }
+}
+
+// The file descriptor of the underlying kqueue instance
+pub struct Selector {
+    raw_fd: i32,
+}
+
+impl Selector {
+    pub fn new() -> Result<Self> {
+        let res = unsafe { kqueue() };
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { raw_fd: res })
+    }
+
+    fn submit(&self, changes: &[RawEvent]) -> Result<()> {
+        match unsafe {
+            kevent(
+                self.raw_fd,
+                changes.as_ptr(),
+                changes.len() as i32,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null(),
+            )
+        } {
+            exit_code if exit_code < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    // Read/write interests map to the `EVFILT_READ`/`EVFILT_WRITE` filters;
+    // edge-triggered and one-shot map to `EV_CLEAR`/`EV_ONESHOT`.
+    pub fn register(&self, fd: i32, token: usize, interest: Interest) -> Result<()> {
+        let mut flags = EV_ADD | EV_ENABLE;
+        if interest.is_edge_triggered() {
+            flags |= EV_CLEAR;
+        }
+        if interest.is_oneshot() {
+            flags |= EV_ONESHOT;
+        }
+
+        let mut changes = Vec::with_capacity(2);
+        if interest.is_readable() {
+            changes.push(RawEvent {
+                ident: fd as usize,
+                filter: EVFILT_READ,
+                flags,
+                fflags: 0,
+                data: 0,
+                udata: token,
+            });
+        }
+        if interest.is_writable() {
+            changes.push(RawEvent {
+                ident: fd as usize,
+                filter: EVFILT_WRITE,
+                flags,
+                fflags: 0,
+                data: 0,
+                udata: token,
+            });
+        }
+
+        self.submit(&changes)
+    }
+
+    // kqueue has no separate "modify" op: resubmitting with EV_ADD updates
+    // the existing filter's flags and udata in place. A filter that is not in
+    // the new interest is deleted first, so the interest set is replaced.
+    pub fn reregister(&self, fd: i32, token: usize, interest: Interest) -> Result<()> {
+        for (wanted, filter) in [
+            (interest.is_readable(), EVFILT_READ),
+            (interest.is_writable(), EVFILT_WRITE),
+        ] {
+            if wanted {
+                continue;
+            }
+            let change = RawEvent {
+                ident: fd as usize,
+                filter,
+                flags: EV_DELETE,
+                fflags: 0,
+                data: 0,
+                udata: 0,
+            };
+            if let Err(err) = self.submit(std::slice::from_ref(&change)) {
+                if err.raw_os_error() != Some(ENOENT) {
+                    return Err(err);
+                }
+            }
+        }
+
+        self.register(fd, token, interest)
+    }
+
+    pub fn deregister(&self, fd: i32) -> Result<()> {
+        for filter in [EVFILT_READ, EVFILT_WRITE] {
+            let change = RawEvent {
+                ident: fd as usize,
+                filter,
+                flags: EV_DELETE,
+                fflags: 0,
+                data: 0,
+                udata: 0,
+            };
+
+            if let Err(err) = self.submit(std::slice::from_ref(&change)) {
+                if err.raw_os_error() != Some(ENOENT) {
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn select(&self, events: &mut Vec<Event>, timeout: Option<i32>) -> Result<()> {
+        let max_events = events.capacity() as i32;
+
+        let deadline = timeout.map(|millis| Timespec {
+            tv_sec: (millis as i64) / 1000,
+            tv_nsec: (millis as i64 % 1000) * 1_000_000,
+        });
+
+        let deadline_ptr = deadline
+            .as_ref()
+            .map_or(std::ptr::null(), |deadline| deadline as *const Timespec);
+
+        let res = unsafe {
+            kevent(
+                self.raw_fd,
+                std::ptr::null(),
+                0,
+                events.as_mut_ptr() as *mut RawEvent,
+                max_events,
+                deadline_ptr,
+            )
+        };
+
+        if res < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        unsafe { events.set_len(res as usize) };
+
+        Ok(())
+    }
+}
+
+impl Drop for Selector {
+    fn drop(&mut self) {
+        let res = unsafe { close(self.raw_fd) };
+
+        if res < 0 {
+            let err = io::Error::last_os_error();
+            eprintln!("ERROR: {err:?}");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+// Portable selector split, one backend per platform, mirroring the way
+// mio lays out `src/sys/unix/selector/{epoll,kqueue}.rs`. `Poll`, `Registry`
+// and `Event` in the parent `poll` module are thin wrappers over whichever
+// `Selector`/`Event` pair is selected here.
+
+#[cfg(target_os = "linux")]
+pub(crate) mod epoll;
+#[cfg(target_os = "linux")]
+pub use epoll::{Event, Selector};
+
+// `RawEvent` below matches the `struct kevent` layout on macOS/iOS/OpenBSD.
+// FreeBSD 11+ and DragonFly append a trailing `ext[4]` field, and NetBSD
+// widens `filter`/`flags` to `uint32_t` and `data` to `int64_t` (shifting
+// every field after it) — neither layout is modeled here, so those targets
+// are deliberately left out until that's done per-OS.
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "openbsd"))]
+pub(crate) mod kqueue;
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "openbsd"))]
+pub use kqueue::{Event, Selector};
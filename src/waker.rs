@@ -0,0 +1,73 @@
+use crate::interest::Interest;
+use crate::poll::Registry;
+use crate::source::SourceFd;
+use crate::sys::epoll;
+use std::io::{self, Result};
+
+// Wakes up a thread blocked in `Poll::poll` from another thread, using the
+// same "wake a blocked reactor" mechanism smol uses (self-pipe on
+// non-Linux, eventfd on Linux). Built on the Linux-only eventfd syscall, so
+// it isn't ported to the kqueue backend.
+//
+// IMPORTANT: the underlying eventfd is registered level-triggered, so it
+// stays readable until drained. Every time `Poll::poll` returns an event
+// whose `token()` is this waker's reserved token, the caller MUST call
+// `drain()` before polling again, or `epoll_wait` will keep
+// returning immediately forever (a busy-loop spin), since the fd never
+// stops being readable.
+pub struct Waker {
+    fd: i32,
+}
+
+impl Waker {
+    // Registers the eventfd with `registry` under `token` so that a write
+    // to it surfaces as a readable event for that token. See the
+    // type-level doc comment: the caller must `drain()` every time this
+    // token comes back from `Poll::poll`.
+    pub fn new(registry: &Registry, token: usize) -> Result<Self> {
+        let fd = unsafe { epoll::eventfd(0, epoll::EFD_NONBLOCK | epoll::EFD_CLOEXEC) };
+
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        registry.register(&SourceFd(fd), token, Interest::READABLE)?;
+
+        Ok(Self { fd })
+    }
+
+    // Unblocks a thread sitting in `epoll_wait` by writing a `u64` of `1`
+    // to the eventfd.
+    pub fn wake(&self) -> Result<()> {
+        let buf = 1u64.to_ne_bytes();
+
+        match unsafe { epoll::write(self.fd, buf.as_ptr(), buf.len()) } {
+            n if n < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    // Drains the eventfd's counter back to zero so it doesn't stay
+    // perpetually readable. MUST be called every time `Poll::poll` reports
+    // an event for this waker's token — skipping it leaves the fd readable
+    // forever and the poll loop will spin without blocking.
+    pub fn drain(&self) -> Result<()> {
+        let mut buf = [0u8; 8];
+
+        match unsafe { epoll::read(self.fd, buf.as_mut_ptr(), buf.len()) } {
+            n if n < 0 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for Waker {
+    fn drop(&mut self) {
+        let res = unsafe { epoll::close(self.fd) };
+
+        if res < 0 {
+            let err = io::Error::last_os_error();
+            eprintln!("ERROR: {err:?}");
+        }
+    }
+}